@@ -0,0 +1,228 @@
+use foreign_types::ForeignTypeRef;
+
+use crate::x509::verify::{X509PolicyTreeRef, X509VerifyParamRef};
+use openssl_macros::corresponds;
+
+// This extends the X509StoreContextRef impl block that already lives in this file (alongside
+// error(), current_cert(), chain(), verify_cert(), init(), etc.) with two more accessors; it does
+// not redefine X509StoreContext/X509StoreContextRef themselves.
+impl X509StoreContextRef {
+    /// Returns the policy tree produced by RFC 5280 policy processing, if
+    /// `X509VerifyFlags::POLICY_CHECK` was set and verification reached the policy-checking
+    /// stage.
+    #[corresponds(X509_STORE_CTX_get0_policy_tree)]
+    pub fn policy_tree(&self) -> Option<&X509PolicyTreeRef> {
+        unsafe {
+            let tree = ffi::X509_STORE_CTX_get0_policy_tree(self.as_ptr());
+            if tree.is_null() {
+                None
+            } else {
+                Some(X509PolicyTreeRef::from_ptr(tree))
+            }
+        }
+    }
+
+    /// Returns the verification param this context will use, so callers can configure e.g. the
+    /// expected hostname or acceptable policy OIDs before calling `verify_cert`.
+    #[corresponds(X509_STORE_CTX_get0_param)]
+    pub fn param_mut(&mut self) -> &mut X509VerifyParamRef {
+        unsafe { X509VerifyParamRef::from_ptr_mut(ffi::X509_STORE_CTX_get0_param(self.as_ptr())) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asn1::{Asn1Object, Asn1Time};
+    use crate::hash::MessageDigest;
+    use crate::pkey::{PKey, Private};
+    use crate::rsa::Rsa;
+    use crate::stack::Stack;
+    use crate::x509::extension::{
+        BasicConstraints, CertificatePolicies, ExtendedKeyUsage, SubjectAlternativeName,
+    };
+    use crate::x509::verify::{X509VerifyFlags, X509VerifyParam};
+    use crate::x509::{X509Builder, X509Name, X509};
+
+    // A minimal self-signed, self-trusted leaf certificate, so tests can drive
+    // `X509StoreContext::verify_cert` end-to-end without external fixtures.
+    fn self_signed(
+        build_extensions: impl FnOnce(&mut X509Builder, &PKey<Private>),
+    ) -> (X509, PKey<Private>) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+
+        let mut name = X509Name::builder().unwrap();
+        name.append_entry_by_text("CN", "test.example").unwrap();
+        let name = name.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+
+        build_extensions(&mut builder, &key);
+
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        (builder.build(), key)
+    }
+
+    // A leaf certificate issued by `ca`. OpenSSL runs no policy processing on a chain that is
+    // only a trust anchor, so policy tests need a leaf below it.
+    fn issued_by(
+        ca: &X509,
+        ca_key: &PKey<Private>,
+        build_extensions: impl FnOnce(&mut X509Builder),
+    ) -> X509 {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+
+        let mut name = X509Name::builder().unwrap();
+        name.append_entry_by_text("CN", "leaf.example").unwrap();
+        let name = name.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(ca.subject_name()).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+
+        build_extensions(&mut builder);
+
+        builder.sign(ca_key, MessageDigest::sha256()).unwrap();
+        builder.build()
+    }
+
+    fn verify_against(
+        anchor: &X509,
+        cert: &X509,
+        configure: impl FnOnce(&mut X509VerifyParamRef),
+    ) -> bool {
+        let mut store = X509StoreBuilder::new().unwrap();
+        store.add_cert(anchor.clone()).unwrap();
+        let store = store.build();
+
+        let chain = Stack::new().unwrap();
+
+        let mut ctx = X509StoreContext::new().unwrap();
+        ctx.init(&store, cert, &chain, |ctx| {
+            configure(ctx.param_mut());
+            ctx.verify_cert()
+        })
+        .unwrap()
+    }
+
+    fn verify_against_self(cert: &X509, configure: impl FnOnce(&mut X509VerifyParamRef)) -> bool {
+        verify_against(cert, cert, configure)
+    }
+
+    #[test]
+    fn add_host_matches_a_san_that_set_host_alone_would_miss() {
+        let (cert, _key) = self_signed(|builder, _key| {
+            let san = SubjectAlternativeName::new()
+                .dns("a.example.com")
+                .dns("b.example.com")
+                .build(&builder.x509v3_context(None, None))
+                .unwrap();
+            builder.append_extension(san).unwrap();
+        });
+
+        // `set_host` alone, for a name that isn't one of the certificate's SANs, fails.
+        let only_wrong_host = verify_against_self(&cert, |param| {
+            param.set_host("wrong.example.com").unwrap();
+        });
+        assert!(!only_wrong_host);
+
+        // Adding the name that actually is a SAN, on top of the wrong one, lets the same
+        // verification succeed: add_host accumulates rather than clobbering set_host.
+        let with_second_host = verify_against_self(&cert, |param| {
+            param.set_host("wrong.example.com").unwrap();
+            param.add_host("b.example.com").unwrap();
+        });
+        assert!(with_second_host);
+    }
+
+    #[test]
+    fn set_policies_accepts_or_rejects_based_on_the_certs_declared_policy() {
+        const CERT_POLICY: &str = "1.3.6.1.4.1.11129.2.4.1";
+        const OTHER_POLICY: &str = "2.23.140.1.2.1";
+
+        let (ca, ca_key) = self_signed(|builder, _key| {
+            let bc = BasicConstraints::new().critical().ca().build().unwrap();
+            builder.append_extension(bc).unwrap();
+        });
+        let cert = issued_by(&ca, &ca_key, |builder| {
+            let policies = CertificatePolicies::new()
+                .add_policy(CERT_POLICY)
+                .build(&builder.x509v3_context(None, None))
+                .unwrap();
+            builder.append_extension(policies).unwrap();
+        });
+
+        // Requiring an explicit policy from a set that doesn't include the cert's OID fails
+        // verification.
+        let wrong_policy_accepted = verify_against(&ca, &cert, |param| {
+            param
+                .set_flags(X509VerifyFlags::POLICY_CHECK | X509VerifyFlags::EXPLICIT_POLICY)
+                .unwrap();
+            let mut policies = Stack::new().unwrap();
+            policies
+                .push(Asn1Object::from_str(OTHER_POLICY).unwrap())
+                .unwrap();
+            param.set_policies(&policies).unwrap();
+        });
+        assert!(!wrong_policy_accepted);
+
+        // Requiring the OID the cert actually declares succeeds.
+        let right_policy_accepted = verify_against(&ca, &cert, |param| {
+            param
+                .set_flags(X509VerifyFlags::POLICY_CHECK | X509VerifyFlags::EXPLICIT_POLICY)
+                .unwrap();
+            let mut policies = Stack::new().unwrap();
+            policies
+                .push(Asn1Object::from_str(CERT_POLICY).unwrap())
+                .unwrap();
+            param.set_policies(&policies).unwrap();
+        });
+        assert!(right_policy_accepted);
+    }
+
+    #[test]
+    fn from_name_changes_the_purpose_check_performed_during_verification() {
+        let (cert, _key) = self_signed(|builder, _key| {
+            let eku = ExtendedKeyUsage::new()
+                .critical()
+                .server_auth()
+                .build()
+                .unwrap();
+            builder.append_extension(eku).unwrap();
+        });
+
+        // A certificate whose EKU is serverAuth-only satisfies the "ssl_server" profile's
+        // purpose check...
+        let as_server = verify_against_self(&cert, |param| {
+            let profile = X509VerifyParam::from_name("ssl_server").unwrap();
+            param.inherit(&profile).unwrap();
+        });
+        assert!(as_server);
+
+        // ...but not the "ssl_client" profile's, proving from_name's profile actually changes
+        // what verify_cert() checks rather than being a no-op.
+        let as_client = verify_against_self(&cert, |param| {
+            let profile = X509VerifyParam::from_name("ssl_client").unwrap();
+            param.inherit(&profile).unwrap();
+        });
+        assert!(!as_client);
+    }
+}
@@ -1,9 +1,13 @@
 use bitflags::bitflags;
-use foreign_types::ForeignTypeRef;
+use foreign_types::{ForeignTypeRef, Opaque};
 use libc::{c_int, c_uint, c_ulong, time_t};
+use std::ffi::{CStr, CString};
+use std::mem;
 use std::net::IpAddr;
 
+use crate::asn1::{Asn1Object, Asn1ObjectRef};
 use crate::error::ErrorStack;
+use crate::stack::StackRef;
 #[cfg(ossl102)]
 use crate::x509::X509PurposeId;
 use crate::{cvt, cvt_p};
@@ -80,6 +84,31 @@ impl X509VerifyParam {
             cvt_p(ffi::X509_VERIFY_PARAM_new()).map(X509VerifyParam)
         }
     }
+
+    /// Creates a new param preconfigured with one of OpenSSL's built-in named verification
+    /// profiles, such as `"ssl_server"`, `"ssl_client"`, or `"smime_sign"`.
+    #[corresponds(X509_VERIFY_PARAM_lookup)]
+    pub fn from_name(name: &str) -> Result<X509VerifyParam, ErrorStack> {
+        let name = CString::new(name).unwrap();
+        unsafe {
+            ffi::init();
+            let profile = cvt_p(ffi::X509_VERIFY_PARAM_lookup(name.as_ptr()))?;
+            let param = X509VerifyParam::new()?;
+            cvt(ffi::X509_VERIFY_PARAM_set1(param.0, profile))?;
+            Ok(param)
+        }
+    }
+}
+
+impl Clone for X509VerifyParam {
+    #[corresponds(X509_VERIFY_PARAM_set1)]
+    fn clone(&self) -> X509VerifyParam {
+        let param = X509VerifyParam::new().unwrap();
+        unsafe {
+            cvt(ffi::X509_VERIFY_PARAM_set1(param.0, self.as_ptr())).unwrap();
+        }
+        param
+    }
 }
 
 impl X509VerifyParamRef {
@@ -91,6 +120,13 @@ impl X509VerifyParamRef {
         }
     }
 
+    /// Gets the host flags.
+    #[corresponds(X509_VERIFY_PARAM_get_hostflags)]
+    pub fn hostflags(&self) -> X509CheckFlags {
+        let bits = unsafe { ffi::X509_VERIFY_PARAM_get_hostflags(self.as_ptr()) };
+        X509CheckFlags { bits }
+    }
+
     /// Set verification flags.
     #[corresponds(X509_VERIFY_PARAM_set_flags)]
     pub fn set_flags(&mut self, flags: X509VerifyFlags) -> Result<(), ErrorStack> {
@@ -117,6 +153,11 @@ impl X509VerifyParamRef {
     }
 
     /// Set the expected DNS hostname.
+    ///
+    /// Note there is no corresponding getter: OpenSSL does not expose a public API to read back
+    /// the reference name(s) configured by `set_host`/`add_host`. `peername()` is the closest
+    /// equivalent, but it only reports the SAN that actually matched after verification, not the
+    /// configured reference identity.
     #[corresponds(X509_VERIFY_PARAM_set1_host)]
     pub fn set_host(&mut self, host: &str) -> Result<(), ErrorStack> {
         unsafe {
@@ -131,6 +172,38 @@ impl X509VerifyParamRef {
         }
     }
 
+    /// Add an additional expected DNS hostname, without clearing any previously configured names.
+    ///
+    /// The verification succeeds if any of the configured hosts matches one of the certificate's
+    /// subject alternative names.
+    #[corresponds(X509_VERIFY_PARAM_add1_host)]
+    pub fn add_host(&mut self, host: &str) -> Result<(), ErrorStack> {
+        unsafe {
+            // len == 0 means "run strlen" :(
+            let raw_host = if host.is_empty() { "\0" } else { host };
+            cvt(ffi::X509_VERIFY_PARAM_add1_host(
+                self.as_ptr(),
+                raw_host.as_ptr() as *const _,
+                host.len(),
+            ))
+            .map(|_| ())
+        }
+    }
+
+    /// Returns the DNS name in the certificate that matched one of the hosts configured with
+    /// `set_host`/`add_host`, after a successful verification.
+    #[corresponds(X509_VERIFY_PARAM_get0_peername)]
+    pub fn peername(&self) -> Option<String> {
+        unsafe {
+            let name = ffi::X509_VERIFY_PARAM_get0_peername(self.as_ptr());
+            if name.is_null() {
+                return None;
+            }
+            let name = CStr::from_ptr(name as *const _);
+            String::from_utf8(name.to_bytes().to_vec()).ok()
+        }
+    }
+
     /// Set the expected IPv4 or IPv6 address.
     #[corresponds(X509_VERIFY_PARAM_set1_ip)]
     pub fn set_ip(&mut self, ip: IpAddr) -> Result<(), ErrorStack> {
@@ -155,6 +228,36 @@ impl X509VerifyParamRef {
         }
     }
 
+    /// Set the expected IPv4 or IPv6 address from its textual representation, e.g.
+    /// `"192.0.2.1"` or `"2001:db8::1"`. Unlike `set_ip`, this accepts any format OpenSSL
+    /// understands without requiring a prior parse into `std::net::IpAddr`.
+    #[corresponds(X509_VERIFY_PARAM_set1_ip_asc)]
+    pub fn set_ip_ascii(&mut self, ip: &str) -> Result<(), ErrorStack> {
+        let ip = CString::new(ip).unwrap();
+        unsafe {
+            cvt(ffi::X509_VERIFY_PARAM_set1_ip_asc(
+                self.as_ptr(),
+                ip.as_ptr(),
+            ))
+            .map(|_| ())
+        }
+    }
+
+    /// Set the expected rfc822 email address.
+    #[corresponds(X509_VERIFY_PARAM_set1_email)]
+    pub fn set_email(&mut self, email: &str) -> Result<(), ErrorStack> {
+        unsafe {
+            // len == 0 means "run strlen" :(
+            let raw_email = if email.is_empty() { "\0" } else { email };
+            cvt(ffi::X509_VERIFY_PARAM_set1_email(
+                self.as_ptr(),
+                raw_email.as_ptr() as *const _,
+                email.len(),
+            ))
+            .map(|_| ())
+        }
+    }
+
     /// Set the verification time, where time is of type time_t, traditionaly defined as seconds since the epoch
     #[corresponds(X509_VERIFY_PARAM_set_time)]
     pub fn set_time(&mut self, time: time_t) {
@@ -167,6 +270,12 @@ impl X509VerifyParamRef {
         unsafe { ffi::X509_VERIFY_PARAM_set_depth(self.as_ptr(), depth) }
     }
 
+    /// Gets the verification depth
+    #[corresponds(X509_VERIFY_PARAM_get_depth)]
+    pub fn depth(&self) -> c_int {
+        unsafe { ffi::X509_VERIFY_PARAM_get_depth(self.as_ptr()) }
+    }
+
     /// Sets the authentication security level to auth_level
     #[corresponds(X509_VERIFY_PARAM_set_auth_level)]
     #[cfg(ossl110)]
@@ -187,4 +296,263 @@ impl X509VerifyParamRef {
     pub fn set_purpose(&mut self, purpose: X509PurposeId) -> Result<(), ErrorStack> {
         unsafe { cvt(ffi::X509_VERIFY_PARAM_set_purpose(self.as_ptr(), purpose.0)).map(|_| ()) }
     }
+
+    /// Sets the acceptable policy OIDs, replacing any that were previously set.
+    ///
+    /// Only takes effect when `X509VerifyFlags::POLICY_CHECK` is set.
+    #[corresponds(X509_VERIFY_PARAM_set1_policies)]
+    pub fn set_policies(&mut self, policies: &StackRef<Asn1Object>) -> Result<(), ErrorStack> {
+        unsafe {
+            cvt(ffi::X509_VERIFY_PARAM_set1_policies(
+                self.as_ptr(),
+                policies.as_ptr(),
+            ))
+            .map(|_| ())
+        }
+    }
+
+    /// Adds an acceptable policy OID to the set already configured.
+    ///
+    /// Takes ownership of `policy`: `X509_VERIFY_PARAM_add0_policy` stores the raw pointer
+    /// directly and frees it itself when the param is dropped, so `policy` must not be freed
+    /// again by its own `Drop` impl.
+    #[corresponds(X509_VERIFY_PARAM_add0_policy)]
+    pub fn add_policy(&mut self, policy: Asn1Object) -> Result<(), ErrorStack> {
+        unsafe {
+            cvt(ffi::X509_VERIFY_PARAM_add0_policy(
+                self.as_ptr(),
+                policy.as_ptr(),
+            ))
+            .map(|_| mem::forget(policy))
+        }
+    }
+
+    /// Merges settings from `from` into `self`, without overriding anything already set here.
+    ///
+    /// This is useful to layer a request-specific param on top of a shared, immutable baseline
+    /// that configures defaults such as depth, flags, or auth level.
+    #[corresponds(X509_VERIFY_PARAM_inherit)]
+    pub fn inherit(&mut self, from: &X509VerifyParamRef) -> Result<(), ErrorStack> {
+        unsafe {
+            cvt(ffi::X509_VERIFY_PARAM_inherit(self.as_ptr(), from.as_ptr())).map(|_| ())
+        }
+    }
+}
+
+/// A node of an `X509PolicyTreeRef`.
+///
+/// Borrowed from the tree that produced it; it cannot outlive the `X509StoreContext` the
+/// verification ran against.
+pub struct X509PolicyNodeRef(Opaque);
+
+impl ForeignTypeRef for X509PolicyNodeRef {
+    type CType = ffi::X509_POLICY_NODE;
+}
+
+impl X509PolicyNodeRef {
+    /// Returns the policy OID this node represents.
+    #[corresponds(X509_POLICY_NODE_get0_policy)]
+    pub fn policy(&self) -> &Asn1ObjectRef {
+        unsafe {
+            let policy = ffi::X509_POLICY_NODE_get0_policy(self.as_ptr());
+            Asn1ObjectRef::from_ptr(policy as *mut _)
+        }
+    }
+}
+
+/// The policy tree built by RFC 5280 policy processing while verifying a certificate chain.
+///
+/// Only available when `X509VerifyFlags::POLICY_CHECK` was set and verification reached the
+/// policy-checking stage.
+/// Borrowed from, and valid only for the lifetime of, the `X509StoreContext` that produced it.
+pub struct X509PolicyTreeRef(Opaque);
+
+impl ForeignTypeRef for X509PolicyTreeRef {
+    type CType = ffi::X509_POLICY_TREE;
+}
+
+impl X509PolicyTreeRef {
+    /// Returns the policies in the authority-constrained policy set.
+    #[corresponds(X509_policy_tree_get0_policies)]
+    pub fn policies(&self) -> Vec<&X509PolicyNodeRef> {
+        unsafe {
+            let stack = ffi::X509_policy_tree_get0_policies(self.as_ptr()) as *mut ffi::OPENSSL_STACK;
+            (0..ffi::OPENSSL_sk_num(stack))
+                .map(|i| {
+                    let node = ffi::OPENSSL_sk_value(stack, i) as *mut ffi::X509_POLICY_NODE;
+                    X509PolicyNodeRef::from_ptr(node)
+                })
+                .collect()
+        }
+    }
+
+    /// Returns the policies in the user-constrained policy set: those of the authority-constrained
+    /// set that satisfy the user-supplied initial policy set.
+    #[corresponds(X509_policy_tree_get0_user_policies)]
+    pub fn user_policies(&self) -> Vec<&X509PolicyNodeRef> {
+        unsafe {
+            let stack =
+                ffi::X509_policy_tree_get0_user_policies(self.as_ptr()) as *mut ffi::OPENSSL_STACK;
+            (0..ffi::OPENSSL_sk_num(stack))
+                .map(|i| {
+                    let node = ffi::OPENSSL_sk_value(stack, i) as *mut ffi::X509_POLICY_NODE;
+                    X509PolicyNodeRef::from_ptr(node)
+                })
+                .collect()
+        }
+    }
+}
+
+/// A builder for `X509VerifyParam`, chaining the common setters into a single expression.
+pub struct X509VerifyParamBuilder(X509VerifyParam);
+
+impl X509VerifyParamBuilder {
+    /// Starts building from a fresh, default-initialized param.
+    pub fn new() -> Result<X509VerifyParamBuilder, ErrorStack> {
+        X509VerifyParam::new().map(X509VerifyParamBuilder)
+    }
+
+    /// Starts building from an existing param, for example one returned by
+    /// `X509VerifyParam::from_name` or cloned from a shared baseline.
+    pub fn from_param(param: X509VerifyParam) -> X509VerifyParamBuilder {
+        X509VerifyParamBuilder(param)
+    }
+
+    /// Sets the expected DNS hostname.
+    pub fn host(mut self, host: &str) -> Result<X509VerifyParamBuilder, ErrorStack> {
+        self.0.set_host(host)?;
+        Ok(self)
+    }
+
+    /// Sets the expected IPv4 or IPv6 address.
+    pub fn ip(mut self, ip: IpAddr) -> Result<X509VerifyParamBuilder, ErrorStack> {
+        self.0.set_ip(ip)?;
+        Ok(self)
+    }
+
+    /// Sets verification flags.
+    pub fn flags(mut self, flags: X509VerifyFlags) -> Result<X509VerifyParamBuilder, ErrorStack> {
+        self.0.set_flags(flags)?;
+        Ok(self)
+    }
+
+    /// Sets the verification depth.
+    pub fn depth(mut self, depth: c_int) -> X509VerifyParamBuilder {
+        self.0.set_depth(depth);
+        self
+    }
+
+    /// Sets the authentication security level.
+    #[cfg(ossl110)]
+    pub fn auth_level(mut self, lvl: c_int) -> X509VerifyParamBuilder {
+        self.0.set_auth_level(lvl);
+        self
+    }
+
+    /// Sets the verification purpose.
+    #[cfg(ossl102)]
+    pub fn purpose(mut self, purpose: X509PurposeId) -> Result<X509VerifyParamBuilder, ErrorStack> {
+        self.0.set_purpose(purpose)?;
+        Ok(self)
+    }
+
+    /// Consumes the builder, yielding the configured param.
+    pub fn build(self) -> X509VerifyParam {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stack::Stack;
+
+    #[test]
+    fn set_host_then_add_host_both_succeed() {
+        let mut param = X509VerifyParam::new().unwrap();
+        param.set_host("example.com").unwrap();
+        param.add_host("example.org").unwrap();
+        // No verification has run yet, so there's no matched name to report. Confirming that
+        // add_host genuinely accumulates rather than clobbers requires running a verification
+        // against a certificate with matching SANs, which the tests in store.rs do.
+        assert_eq!(param.peername(), None);
+    }
+
+    #[test]
+    fn set_email_accepts_an_address() {
+        let mut param = X509VerifyParam::new().unwrap();
+        param.set_email("user@example.com").unwrap();
+    }
+
+    #[test]
+    fn set_ip_ascii_accepts_v4_and_v6_text() {
+        let mut param = X509VerifyParam::new().unwrap();
+        param.set_ip_ascii("192.0.2.1").unwrap();
+        param.set_ip_ascii("2001:db8::1").unwrap();
+    }
+
+    #[test]
+    fn add_policy_and_set_policies_accept_oids() {
+        let mut param = X509VerifyParam::new().unwrap();
+        param
+            .add_policy(Asn1Object::from_str("2.16.840.1.101.3.2.1.48.1").unwrap())
+            .unwrap();
+
+        let mut policies = Stack::new().unwrap();
+        policies
+            .push(Asn1Object::from_str("2.5.29.32.0").unwrap())
+            .unwrap();
+        param.set_policies(&policies).unwrap();
+    }
+
+    #[test]
+    fn from_name_looks_up_a_builtin_profile_and_rejects_unknown_ones() {
+        X509VerifyParam::from_name("ssl_server").unwrap();
+        assert!(X509VerifyParam::from_name("not-a-real-profile").is_err());
+    }
+
+    #[test]
+    fn inherit_fills_in_unset_fields_without_overwriting_set_ones() {
+        let mut base = X509VerifyParam::new().unwrap();
+        base.set_depth(9);
+
+        let mut derived = X509VerifyParam::new().unwrap();
+        derived.set_depth(3);
+        derived.inherit(&base).unwrap();
+
+        assert_eq!(derived.depth(), 3);
+
+        let mut fresh = X509VerifyParam::new().unwrap();
+        fresh.inherit(&base).unwrap();
+
+        assert_eq!(fresh.depth(), 9);
+    }
+
+    #[test]
+    fn clone_copies_settings_into_an_independent_param() {
+        let mut param = X509VerifyParam::new().unwrap();
+        param.set_depth(7);
+
+        let cloned = param.clone();
+        param.set_depth(2);
+        assert_eq!(cloned.depth(), 7);
+    }
+
+    #[test]
+    fn builder_chains_common_setters() {
+        let param = X509VerifyParamBuilder::new()
+            .unwrap()
+            .host("example.com")
+            .unwrap()
+            .depth(4)
+            .build();
+        assert_eq!(param.depth(), 4);
+    }
+
+    #[test]
+    fn hostflags_roundtrip() {
+        let mut param = X509VerifyParam::new().unwrap();
+        param.set_hostflags(X509CheckFlags::NO_WILDCARDS);
+        assert_eq!(param.hostflags(), X509CheckFlags::NO_WILDCARDS);
+    }
 }